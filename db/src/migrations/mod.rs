@@ -0,0 +1,135 @@
+use ehandle::db::{MigrationError, MigrationErrorKind};
+use min_sqlite3_sys::prelude::*;
+use std::{path::Path, process};
+
+mod v0001_core;
+mod v0002_package_state;
+
+/// A single, ordered schema change. `target_version` must match this step's
+/// position in [`registry`] (index 0 -> version 1, index 1 -> version 2,
+/// ...), since that is what's compared against `PRAGMA user_version`.
+trait MigrationStep {
+    fn target_version(&self) -> i64;
+    fn up(&self, db: &Database) -> Result<(), MigrationError>;
+}
+
+/// Ordered list of every migration this binary knows how to apply. Append a
+/// new step here when adding a schema change; existing steps must never be
+/// edited once released, since `PRAGMA user_version` on a user's machine may
+/// already be past them.
+fn registry() -> Vec<Box<dyn MigrationStep>> {
+    vec![
+        Box::new(v0001_core::CoreTables),
+        Box::new(v0002_package_state::PackageStateColumn),
+    ]
+}
+
+pub fn start_db_migrations() -> Result<(), MigrationError> {
+    let db = Database::open(Path::new(super::DB_PATH))?;
+
+    let current_version = get_migration_version(&db)?;
+
+    for step in registry() {
+        if step.target_version() <= current_version {
+            continue;
+        }
+
+        run_step(&db, step.as_ref())?;
+    }
+
+    db.close();
+
+    Ok(())
+}
+
+fn run_step(db: &Database, step: &dyn MigrationStep) -> Result<(), MigrationError> {
+    execute(db, "BEGIN TRANSACTION;")?;
+
+    if let Err(err) = step.up(db) {
+        execute(db, "ROLLBACK;")?;
+        return Err(err);
+    }
+
+    if let Err(err) = set_migration_version(db, step.target_version()) {
+        execute(db, "ROLLBACK;")?;
+        return Err(err);
+    }
+
+    execute(db, "COMMIT;")?;
+
+    Ok(())
+}
+
+#[inline]
+fn execute(db: &Database, statement: &str) -> Result<(), MigrationError> {
+    db.execute(
+        statement.to_owned(),
+        None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+    )?;
+
+    Ok(())
+}
+
+#[inline]
+fn set_migration_version(db: &Database, version: i64) -> Result<(), MigrationError> {
+    let statement = format!("PRAGMA user_version = {};", version);
+    let status = db.execute(
+        statement,
+        None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+    )?;
+
+    if status != SqlitePrimaryResult::Ok {
+        return Err(MigrationError::new(MigrationErrorKind::VersionCouldNotSet));
+    }
+
+    Ok(())
+}
+
+#[inline]
+fn get_migration_version(db: &Database) -> Result<i64, MigrationError> {
+    let statement = String::from("PRAGMA user_version;");
+
+    let mut sql = db.prepare(
+        statement,
+        None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+    )?;
+
+    let mut version = 0;
+    while let PreparedStatementStatus::FoundRow = sql.execute_prepared() {
+        version = sql.clone().get_data::<i64>(0).unwrap();
+    }
+
+    sql.kill();
+
+    Ok(version)
+}
+
+#[inline]
+pub(crate) fn callback_function(status: SqlitePrimaryResult, sql_statement: String) {
+    println!(
+        "SQL EXECUTION HAS BEEN FAILED.\n\nReason: {:?}\nStatement: {}",
+        status, sql_statement
+    );
+
+    process::exit(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `start_db_migrations` trusts that `registry()` is ordered with no gaps
+    /// (index 0 -> version 1, index 1 -> version 2, ...), since it only
+    /// compares a step's own `target_version` against the current
+    /// `PRAGMA user_version`. A step out of order or skipped would silently
+    /// never run, or run at the wrong time relative to its neighbours.
+    #[test]
+    fn registry_targets_are_contiguous_and_ascending() {
+        let steps = registry();
+        assert!(!steps.is_empty());
+
+        for (index, step) in steps.iter().enumerate() {
+            assert_eq!(step.target_version(), (index + 1) as i64);
+        }
+    }
+}
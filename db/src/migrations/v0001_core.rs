@@ -0,0 +1,111 @@
+use super::{callback_function, MigrationStep};
+use ehandle::db::MigrationError;
+use min_sqlite3_sys::prelude::*;
+
+/// Creates the tables lpm needs from a completely empty database: `sys`,
+/// `checksum_kinds`, `package_kinds`, `package_repositories`, `packages`
+/// and `files`.
+pub(super) struct CoreTables;
+
+impl MigrationStep for CoreTables {
+    fn target_version(&self) -> i64 {
+        1
+    }
+
+    fn up(&self, db: &Database) -> Result<(), MigrationError> {
+        let statement = String::from(
+            "
+                PRAGMA foreign_keys = on;
+
+                /*
+                 * Statement of `sys` table creation.
+                 * This table will hold the core informations about lpm.
+                */
+                CREATE TABLE sys (
+                   id            INTEGER    PRIMARY KEY    AUTOINCREMENT,
+                   name          TEXT       NOT NULL,
+                   v_major       INTEGER    NOT NULL,
+                   v_minor       INTEGER    NOT NULL,
+                   v_patch       INTEGER    NOT NULL,
+                   v_tag         TEXT,
+                   v_readable    TEXT       NOT NULL
+                );
+
+                /*
+                 * Statement of `checksum_kinds` table creation.
+                 * This table will hold the supported hashing algorithms
+                 * for the packages.
+                */
+                CREATE TABLE checksum_kinds (
+                   id      INTEGER    PRIMARY KEY    AUTOINCREMENT,
+                   kind    TEXT       NOT NULL
+                );
+
+                /*
+                 * Statement of `package_kinds` table creation.
+                 * This table will hold the kind of packages to help
+                 * classify the packages installed in the system.
+                */
+                CREATE TABLE package_kinds (
+                   id      INTEGER    PRIMARY KEY    AUTOINCREMENT,
+                   kind    TEXT       NOT NULL
+                );
+
+                /*
+                 * Statement of `package_repositories` table creation.
+                 * This table will hold the repository informations.
+                */
+                CREATE TABLE package_repositories (
+                   id            INTEGER    PRIMARY KEY    AUTOINCREMENT,
+                   repository    TEXT       NOT NULL
+                );
+
+                /*
+                 * Statement of `packages` table creation.
+                 * This table will hold installed package informations.
+                */
+                CREATE TABLE packages (
+                   id                       INTEGER    PRIMARY KEY    AUTOINCREMENT,
+                   name                     TEXT       NOT NULL,
+                   description              TEXT,
+                   maintainer               TEXT       NOT NULL,
+                   repository_id            INTEGER,
+                   homepage                 TEXT,
+                   depended_package_id      INTEGER,
+                   package_kind_id          INTEGER    NOT_NULL,
+                   installed_size           INTEGER    NOT_NULL,
+                   license                  TEXT       NOT_NULL,
+                   v_major                  INTEGER    NOT NULL,
+                   v_minor                  INTEGER    NOT NULL,
+                   v_patch                  INTEGER    NOT NULL,
+                   v_tag                    TEXT,
+                   v_readable               TEXT       NOT NULL,
+
+                   FOREIGN KEY(repository_id) REFERENCES package_repositories(id),
+                   FOREIGN KEY(depended_package_id) REFERENCES packages(id),
+                   FOREIGN KEY(package_kind_id) REFERENCES package_kinds(id)
+                );
+
+                /*
+                 * Statement of `files` table creation.
+                 * This table will hold the information of files which are in the
+                 * packages.
+                */
+                CREATE TABLE files (
+                   id                  INTEGER    PRIMARY KEY    AUTOINCREMENT,
+                   name                TEXT       NOT NULL,
+                   absolute_path       TEXT       NOT NULL,
+                   checksum            TEXT       NOT NULL,
+                   checksum_kind_id    INTEGER    NOT NULL,
+                   package_id          INTEGER    NOT NULL,
+                   FOREIGN KEY(package_id) REFERENCES packages(id),
+                   FOREIGN KEY(checksum_kind_id) REFERENCES checksum_kinds(id)
+                );
+            ",
+        );
+
+        db.execute(statement, Some(callback_function))?;
+
+        Ok(())
+    }
+}
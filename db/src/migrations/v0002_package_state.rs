@@ -0,0 +1,26 @@
+use super::{callback_function, MigrationStep};
+use ehandle::db::MigrationError;
+use min_sqlite3_sys::prelude::*;
+
+/// Adds a `state` column to `packages` so an install can be resumed or
+/// rolled back after a crash instead of leaving orphaned files on disk with
+/// no corresponding database row.
+pub(super) struct PackageStateColumn;
+
+impl MigrationStep for PackageStateColumn {
+    fn target_version(&self) -> i64 {
+        2
+    }
+
+    fn up(&self, db: &Database) -> Result<(), MigrationError> {
+        let statement = String::from(
+            "
+                ALTER TABLE packages ADD COLUMN state TEXT NOT NULL DEFAULT 'installed';
+            ",
+        );
+
+        db.execute(statement, Some(callback_function))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,380 @@
+use crate::sql_builder::{Direction, Limit, OrderBy};
+use common::pkg::PkgDataFromFs;
+use ehandle::{lpm::LpmError, MainError};
+use min_sqlite3_sys::prelude::*;
+use std::path::Path;
+
+/// Escapes a value interpolated into a hand-built SQL string literal by
+/// doubling single quotes, SQLite's own escaping rule. Used instead of
+/// bound parameters because the `?N` placeholder support `sql-builder`
+/// emits isn't wired up to these hand-written statements; this is the
+/// minimum needed to stop a value such as a package's own file path (`files`
+/// rows are built from the package's own manifest, which is not trusted
+/// input) from breaking out of its literal.
+fn escape_sql_string(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
+/// Lifecycle of a package row's `state` column, written as `install_from_repository`
+/// progresses so an interrupted install can be detected and recovered on the
+/// next startup instead of leaving files on disk with no database record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageState {
+    Downloading,
+    Extracting,
+    Installing,
+    Installed,
+    Failed,
+}
+
+impl PackageState {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PackageState::Downloading => "downloading",
+            PackageState::Extracting => "extracting",
+            PackageState::Installing => "installing",
+            PackageState::Installed => "installed",
+            PackageState::Failed => "failed",
+        }
+    }
+
+    fn from_str(value: &str) -> Self {
+        match value {
+            "downloading" => PackageState::Downloading,
+            "extracting" => PackageState::Extracting,
+            "installing" => PackageState::Installing,
+            "installed" => PackageState::Installed,
+            _ => PackageState::Failed,
+        }
+    }
+}
+
+/// A package row left in a non-`installed` state, as found by [`get_packages_not_in_state`].
+#[derive(Debug, Clone)]
+pub struct RecoverablePackage {
+    pub id: i64,
+    pub name: String,
+    pub state: PackageState,
+}
+
+/// Updates a package's lifecycle state. Called outside of the install
+/// transaction so it is durable even if a later phase of the install fails
+/// or the process is killed.
+#[inline]
+pub fn set_package_state(
+    db: &Database,
+    pkg_id: i64,
+    state: PackageState,
+) -> Result<(), LpmError<MainError>> {
+    let statement = format!(
+        "UPDATE packages SET state = '{}' WHERE id = {};",
+        state.as_str(),
+        pkg_id
+    );
+
+    db.execute(
+        statement,
+        None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+    )?;
+
+    Ok(())
+}
+
+/// Lists every package row whose `state` is not `installed`, i.e. packages
+/// left behind by a crash or kill mid-`install_from_repository`. Ordered by
+/// name so `recover()`'s log output is stable across runs.
+#[inline]
+pub fn get_packages_not_in_state(
+    db: &Database,
+    state: PackageState,
+) -> Result<Vec<RecoverablePackage>, LpmError<MainError>> {
+    let order_by = OrderBy(vec![("name".to_owned(), Direction::Asc)]);
+    let statement = format!(
+        "SELECT id, name, state FROM packages WHERE state != '{}' {};",
+        state.as_str(),
+        order_by
+    );
+
+    let mut sql = db.prepare(
+        statement,
+        None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+    )?;
+
+    let mut packages = Vec::new();
+    while let PreparedStatementStatus::FoundRow = sql.execute_prepared() {
+        packages.push(RecoverablePackage {
+            id: sql.clone().get_data(0).unwrap(),
+            name: sql.clone().get_data(1).unwrap(),
+            state: PackageState::from_str(&sql.clone().get_data::<String>(2).unwrap()),
+        });
+    }
+
+    sql.kill();
+
+    Ok(packages)
+}
+
+/// Deletes a package row along with its `files` rows. Used when recovering
+/// from a crashed install that never made it to the `installed` state.
+#[inline]
+pub fn delete_package(db: &Database, pkg_id: i64) -> Result<(), LpmError<MainError>> {
+    db.execute(
+        format!("DELETE FROM files WHERE package_id = {};", pkg_id),
+        None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+    )?;
+
+    db.execute(
+        format!("DELETE FROM packages WHERE id = {};", pkg_id),
+        None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+    )?;
+
+    Ok(())
+}
+
+/// Minimal, comparable view of a package's version fields as stored in the
+/// `packages` table. Kept separate from the richer `common::version::Version`
+/// used for index parsing so this module does not need to know about
+/// version conditions.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct InstalledVersion {
+    pub major: i64,
+    pub minor: i64,
+    pub patch: i64,
+}
+
+/// An already installed package as known by the local `packages` table.
+#[derive(Debug, Clone)]
+pub struct InstalledPackage {
+    pub id: i64,
+    pub version: InstalledVersion,
+}
+
+/// Looks up an installed package by name, returning its row id and version.
+/// Returns `None` when the package is not present, mirroring
+/// `is_package_exists`'s "not found" semantics.
+#[inline]
+pub fn get_installed_package(
+    db: &Database,
+    pkg_name: &str,
+) -> Result<Option<InstalledPackage>, LpmError<MainError>> {
+    let statement = format!(
+        "SELECT id, v_major, v_minor, v_patch FROM packages WHERE name = '{}' {};",
+        escape_sql_string(pkg_name),
+        Limit(1, None)
+    );
+
+    let mut sql = db.prepare(
+        statement,
+        None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+    )?;
+
+    let mut pkg = None;
+    if let PreparedStatementStatus::FoundRow = sql.execute_prepared() {
+        pkg = Some(InstalledPackage {
+            id: sql.clone().get_data(0).unwrap(),
+            version: InstalledVersion {
+                major: sql.clone().get_data(1).unwrap(),
+                minor: sql.clone().get_data(2).unwrap(),
+                patch: sql.clone().get_data(3).unwrap(),
+            },
+        });
+    }
+
+    sql.kill();
+
+    Ok(pkg)
+}
+
+/// Returns the `absolute_path` of every file currently recorded for an
+/// installed package.
+#[inline]
+pub fn get_package_file_paths(
+    db: &Database,
+    pkg_id: i64,
+) -> Result<Vec<String>, LpmError<MainError>> {
+    let statement = format!("SELECT absolute_path FROM files WHERE package_id = {};", pkg_id);
+
+    let mut sql = db.prepare(
+        statement,
+        None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+    )?;
+
+    let mut paths = Vec::new();
+    while let PreparedStatementStatus::FoundRow = sql.execute_prepared() {
+        paths.push(sql.clone().get_data(0).unwrap());
+    }
+
+    sql.kill();
+
+    Ok(paths)
+}
+
+/// A file recorded for an installed package, with enough information to
+/// re-verify its on-disk digest against what was recorded at install time.
+#[derive(Debug, Clone)]
+pub struct InstalledFileChecksum {
+    pub absolute_path: String,
+    pub checksum: String,
+    pub checksum_kind: String,
+}
+
+/// Joins `files` with `checksum_kinds` for an installed package, returning
+/// everything a `verify`-style check needs to re-hash each file.
+#[inline]
+pub fn get_package_file_checksums(
+    db: &Database,
+    pkg_id: i64,
+) -> Result<Vec<InstalledFileChecksum>, LpmError<MainError>> {
+    let statement = format!(
+        "SELECT f.absolute_path, f.checksum, k.kind \
+         FROM files f \
+         JOIN checksum_kinds k ON f.checksum_kind_id = k.id \
+         WHERE f.package_id = {};",
+        pkg_id
+    );
+
+    let mut sql = db.prepare(
+        statement,
+        None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+    )?;
+
+    let mut files = Vec::new();
+    while let PreparedStatementStatus::FoundRow = sql.execute_prepared() {
+        files.push(InstalledFileChecksum {
+            absolute_path: sql.clone().get_data(0).unwrap(),
+            checksum: sql.clone().get_data(1).unwrap(),
+            checksum_kind: sql.clone().get_data(2).unwrap(),
+        });
+    }
+
+    sql.kill();
+
+    Ok(files)
+}
+
+/// Deletes the `files` rows for an installed package whose `absolute_path`
+/// is no longer part of the package (used by upgrade reconciliation).
+#[inline]
+pub fn delete_file_records(
+    db: &Database,
+    pkg_id: i64,
+    absolute_paths: &[String],
+) -> Result<(), LpmError<MainError>> {
+    for path in absolute_paths {
+        let statement = format!(
+            "DELETE FROM files WHERE package_id = {} AND absolute_path = '{}';",
+            pkg_id,
+            escape_sql_string(path)
+        );
+
+        db.execute(
+            statement,
+            None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Upgrade-path counterpart to `insert_to_db`: updates an already
+/// installed package's row in place instead of inserting a fresh one, and
+/// replaces the `files` row of every path carried by this build. Kept as its own trait
+/// rather than a new method on the existing insert-side trait so it
+/// doesn't need to touch that trait or its implementors.
+pub trait DbOpsForUpdateFile {
+    fn update_in_db(&self, db: &Database, pkg_id: i64) -> Result<i64, LpmError<MainError>>;
+}
+
+impl DbOpsForUpdateFile for PkgDataFromFs {
+    fn update_in_db(&self, db: &Database, pkg_id: i64) -> Result<i64, LpmError<MainError>> {
+        let version = &self.meta_dir.meta.version;
+        let tag_sql = match &version.tag {
+            Some(tag) => format!("'{}'", escape_sql_string(tag)),
+            None => "NULL".to_owned(),
+        };
+
+        let statement = format!(
+            "UPDATE packages SET v_major = {}, v_minor = {}, v_patch = {}, v_tag = {}, v_readable = '{}' WHERE id = {};",
+            version.major,
+            version.minor,
+            version.patch,
+            tag_sql,
+            escape_sql_string(&version.readable_format),
+            pkg_id
+        );
+
+        db.execute(
+            statement,
+            None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+        )?;
+
+        for file in &self.meta_dir.files.0 {
+            let absolute_path = Path::new("/").join(&file.path).display().to_string();
+            let name = file
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| absolute_path.clone());
+            let checksum_kind_id = get_or_insert_checksum_kind_id(db, &file.checksum_kind.to_string())?;
+
+            delete_file_records(db, pkg_id, &[absolute_path.clone()])?;
+
+            let statement = format!(
+                "INSERT INTO files (name, absolute_path, checksum, checksum_kind_id, package_id) \
+                 VALUES ('{}', '{}', '{}', {}, {});",
+                escape_sql_string(&name),
+                escape_sql_string(&absolute_path),
+                escape_sql_string(&file.checksum),
+                checksum_kind_id,
+                pkg_id
+            );
+
+            db.execute(
+                statement,
+                None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+            )?;
+        }
+
+        Ok(pkg_id)
+    }
+}
+
+/// Looks up `checksum_kinds.id` for `kind`, inserting the row the first
+/// time this kind is seen.
+fn get_or_insert_checksum_kind_id(db: &Database, kind: &str) -> Result<i64, LpmError<MainError>> {
+    if let Some(id) = find_checksum_kind_id(db, kind)? {
+        return Ok(id);
+    }
+
+    db.execute(
+        format!(
+            "INSERT INTO checksum_kinds (kind) VALUES ('{}');",
+            escape_sql_string(kind)
+        ),
+        None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+    )?;
+
+    Ok(find_checksum_kind_id(db, kind)?.unwrap())
+}
+
+fn find_checksum_kind_id(db: &Database, kind: &str) -> Result<Option<i64>, LpmError<MainError>> {
+    let statement = format!(
+        "SELECT id FROM checksum_kinds WHERE kind = '{}' {};",
+        escape_sql_string(kind),
+        Limit(1, None)
+    );
+
+    let mut sql = db.prepare(
+        statement,
+        None::<Box<dyn FnOnce(SqlitePrimaryResult, String)>>,
+    )?;
+
+    let mut id = None;
+    if let PreparedStatementStatus::FoundRow = sql.execute_prepared() {
+        id = Some(sql.clone().get_data(0).unwrap());
+    }
+
+    sql.kill();
+
+    Ok(id)
+}
@@ -106,6 +106,76 @@ pub trait WhereInstructions {
 
     /// Adds contiditon as 'OR'
     fn or_where(&self, w: Where) -> Self;
+
+    /// Adds contiditon as 'AND' only when `Some`, otherwise a no-op. Lets
+    /// callers compose optional filters without special-casing the
+    /// dangling 'AND' a `None` would otherwise leave behind.
+    fn and_where_option(&self, w: Option<Where>) -> Self
+    where
+        Self: Sized + Clone,
+    {
+        match w {
+            Some(w) => self.and_where(w),
+            None => self.clone(),
+        }
+    }
+}
+
+/// Sort direction for an [`OrderBy`] column.
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::Asc => write!(f, "ASC"),
+            Direction::Desc => write!(f, "DESC"),
+        }
+    }
+}
+
+/// `ORDER BY` clause, emitted after the `WHERE` clause. Column order in the
+/// `Vec` is preserved as the sort priority.
+pub struct OrderBy(pub Vec<(String, Direction)>);
+
+impl Display for OrderBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            common::log_and_panic!("At least one column must be defined for ORDER BY clauses.");
+        }
+
+        let columns: Vec<String> = self
+            .0
+            .iter()
+            .map(|(column, direction)| format!("{} {}", column, direction))
+            .collect();
+
+        write!(f, "ORDER BY {}", columns.join(", "))
+    }
+}
+
+/// `LIMIT`/`OFFSET` clause, emitted after the `ORDER BY` clause.
+pub struct Limit(pub u32, pub Option<u32>);
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.1 {
+            Some(offset) => write!(f, "LIMIT {} OFFSET {}", self.0, offset),
+            None => write!(f, "LIMIT {}", self.0),
+        }
+    }
+}
+
+pub trait OrderByInstructions {
+    /// Adds 'ORDER BY' clause
+    fn order_by(&self, order_by: OrderBy) -> Self;
+}
+
+pub trait LimitInstructions {
+    /// Adds 'LIMIT'/'OFFSET' clause
+    fn limit(&self, limit: Limit) -> Self;
 }
 
 impl Display for Where {
@@ -0,0 +1,48 @@
+use min_sqlite3_sys::prelude::*;
+use std::fmt::Display;
+
+/// Error kinds surfaced while applying `db::migrations`.
+#[derive(Debug)]
+pub enum MigrationErrorKind {
+    VersionCouldNotSet,
+    SqliteError(String),
+}
+
+impl Display for MigrationErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationErrorKind::VersionCouldNotSet => {
+                write!(f, "Migration version could not be set.")
+            }
+            MigrationErrorKind::SqliteError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// Kept separate from [`crate::lpm::LpmError`] since migrations run before
+/// the install pipeline exists and have no package/repository context to
+/// wrap.
+#[derive(Debug)]
+pub struct MigrationError {
+    pub kind: MigrationErrorKind,
+}
+
+impl MigrationError {
+    pub fn new(kind: MigrationErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl Display for MigrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+impl<'a> From<MinSqliteWrapperError<'a>> for MigrationError {
+    fn from(err: MinSqliteWrapperError<'a>) -> Self {
+        MigrationError::new(MigrationErrorKind::SqliteError(err.to_string()))
+    }
+}
@@ -0,0 +1,78 @@
+pub mod db;
+pub mod lpm;
+pub mod pkg;
+pub mod repository;
+
+use lpm::LpmError;
+use min_sqlite3_sys::prelude::*;
+use std::fmt::Display;
+
+/// Top-level error kind every public `lpm` function surfaces through
+/// [`lpm::LpmError`]. Wraps the error kinds of the crates it depends on plus
+/// the stdlib/SQLite errors its plumbing hits directly.
+#[derive(Debug)]
+pub enum MainError {
+    Package(pkg::PackageErrorKind),
+    Repository(repository::RepositoryErrorKind),
+    Io(std::io::Error),
+    Sqlite(String),
+}
+
+impl Display for MainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MainError::Package(kind) => write!(f, "{}", kind),
+            MainError::Repository(kind) => write!(f, "{}", kind),
+            MainError::Io(err) => write!(f, "{}", err),
+            MainError::Sqlite(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// Implemented by every error kind enum so a bare kind can be turned into
+/// the [`lpm::LpmError`] wrapper with `.to_lpm_err()` instead of repeating
+/// `LpmError::new(..)` at every call site.
+pub trait ErrorCommons {
+    fn to_lpm_err(self) -> LpmError<Self>
+    where
+        Self: Sized,
+    {
+        LpmError::new(self)
+    }
+}
+
+impl From<std::io::Error> for MainError {
+    fn from(err: std::io::Error) -> Self {
+        MainError::Io(err)
+    }
+}
+
+impl From<std::io::Error> for LpmError<MainError> {
+    fn from(err: std::io::Error) -> Self {
+        LpmError::new(MainError::from(err))
+    }
+}
+
+impl<'a> From<MinSqliteWrapperError<'a>> for MainError {
+    fn from(err: MinSqliteWrapperError<'a>) -> Self {
+        MainError::Sqlite(err.to_string())
+    }
+}
+
+impl<'a> From<MinSqliteWrapperError<'a>> for LpmError<MainError> {
+    fn from(err: MinSqliteWrapperError<'a>) -> Self {
+        LpmError::new(MainError::from(err))
+    }
+}
+
+impl From<LpmError<pkg::PackageErrorKind>> for LpmError<MainError> {
+    fn from(err: LpmError<pkg::PackageErrorKind>) -> Self {
+        LpmError::new(MainError::Package(err.kind))
+    }
+}
+
+impl From<LpmError<repository::RepositoryErrorKind>> for LpmError<MainError> {
+    fn from(err: LpmError<repository::RepositoryErrorKind>) -> Self {
+        LpmError::new(MainError::Repository(err.kind))
+    }
+}
@@ -0,0 +1,23 @@
+use std::fmt::Display;
+
+/// Wraps an error kind (e.g. [`crate::pkg::PackageErrorKind`],
+/// [`crate::MainError`]) so every fallible `lpm` function can share a single
+/// error shape regardless of which kind enum it ultimately returns.
+#[derive(Debug)]
+pub struct LpmError<T> {
+    pub kind: T,
+}
+
+impl<T> LpmError<T> {
+    pub fn new(kind: T) -> Self {
+        Self { kind }
+    }
+}
+
+impl<T: Display> Display for LpmError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl<T: Display + std::fmt::Debug> std::error::Error for LpmError<T> {}
@@ -0,0 +1,41 @@
+use crate::ErrorCommons;
+use std::fmt::Display;
+
+/// Error kinds surfaced by package resolution, installation and
+/// verification.
+#[derive(Debug)]
+pub enum PackageErrorKind {
+    InvalidPackageName(String),
+    PackageNotInstalled(String),
+    DependencyCycle,
+    DependencyFailed(String),
+    ChecksumMismatch(String),
+    UnsupportedChecksumKind(String),
+}
+
+impl Display for PackageErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackageErrorKind::InvalidPackageName(name) => {
+                write!(f, "'{}' is not a valid package name.", name)
+            }
+            PackageErrorKind::PackageNotInstalled(name) => {
+                write!(f, "Package '{}' is not installed.", name)
+            }
+            PackageErrorKind::DependencyCycle => {
+                write!(f, "Package dependency graph contains a cycle.")
+            }
+            PackageErrorKind::DependencyFailed(name) => {
+                write!(f, "Dependency '{}' failed to install.", name)
+            }
+            PackageErrorKind::ChecksumMismatch(path) => {
+                write!(f, "Checksum mismatch detected for '{}'.", path)
+            }
+            PackageErrorKind::UnsupportedChecksumKind(kind) => {
+                write!(f, "'{}' is not a supported checksum kind.", kind)
+            }
+        }
+    }
+}
+
+impl ErrorCommons for PackageErrorKind {}
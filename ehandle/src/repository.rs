@@ -0,0 +1,21 @@
+use crate::ErrorCommons;
+use std::fmt::Display;
+
+/// Error kinds surfaced while resolving packages against configured
+/// repositories.
+#[derive(Debug)]
+pub enum RepositoryErrorKind {
+    PackageNotFound(String),
+}
+
+impl Display for RepositoryErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepositoryErrorKind::PackageNotFound(name) => {
+                write!(f, "Package '{}' could not be found in any repository.", name)
+            }
+        }
+    }
+}
+
+impl ErrorCommons for RepositoryErrorKind {}
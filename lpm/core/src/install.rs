@@ -11,7 +11,11 @@ use common::{
     some_or_error,
 };
 use db::{
-    pkg::{is_package_exists, DbOpsForBuildFile},
+    pkg::{
+        delete_file_records, delete_package, get_installed_package, get_package_file_paths,
+        is_package_exists, set_package_state, DbOpsForBuildFile, DbOpsForUpdateFile,
+        InstalledVersion, PackageState,
+    },
     transaction_op, PkgIndex, Transaction,
 };
 use ehandle::{
@@ -20,17 +24,31 @@ use ehandle::{
 use logger::{debug, info, warning};
 use min_sqlite3_sys::prelude::*;
 use std::{
+    collections::{HashMap, VecDeque},
     fs::{self, create_dir_all},
     path::{Path, PathBuf},
-    sync::{Arc, RwLock},
+    sync::{Arc, Condvar, Mutex},
     thread,
 };
 
+/// A package resolved from the repository index together with the names of
+/// its direct (non-transitive) dependencies. A `Vec` of these forms the
+/// dependency DAG that [`install_from_repository`] installs in topological
+/// order.
+struct PkgDependencyNode {
+    pkg: PkgIndex,
+    dependency_names: Vec<String>,
+}
+
 trait PkgInstallTasks {
     fn get_pkg_stack(
         core_db: &Database,
         pkg_to_query: PkgToQuery,
-    ) -> Result<Vec<PkgIndex>, LpmError<MainError>>;
+    ) -> Result<Vec<PkgDependencyNode>, LpmError<MainError>>;
+    fn get_pkg_stack_many(
+        core_db: &Database,
+        pkg_to_queries: Vec<PkgToQuery>,
+    ) -> Result<Vec<PkgDependencyNode>, LpmError<MainError>>;
     fn pre_install_task(path: &Path) -> Result<Self, LpmError<MainError>>
     where
         Self: Sized;
@@ -39,26 +57,60 @@ trait PkgInstallTasks {
         core_db: &Database,
         src_pkg_id: Option<i64>,
     ) -> Result<i64, LpmError<MainError>>;
+    fn verify_checksums(&self) -> Result<(), LpmError<MainError>>;
     fn copy_programs(&self) -> Result<(), LpmError<MainError>>;
     fn copy_scripts(&self) -> Result<(), LpmError<MainError>>;
     fn install(&self) -> Result<(), LpmError<MainError>>;
+    fn upgrade_install_task(
+        &self,
+        core_db: &Database,
+        installed_pkg_id: i64,
+    ) -> Result<i64, LpmError<MainError>>;
 }
 
 impl PkgInstallTasks for PkgDataFromFs {
-    /// Finds package dependencies and returns it with the package it self.
+    /// Finds package dependencies and returns the dependency DAG rooted at
+    /// the requested package: one node per distinct package, each carrying
+    /// the names of its direct dependencies.
     fn get_pkg_stack(
         core_db: &Database,
         pkg_to_query: PkgToQuery,
-    ) -> Result<Vec<PkgIndex>, LpmError<MainError>> {
+    ) -> Result<Vec<PkgDependencyNode>, LpmError<MainError>> {
+        Self::get_pkg_stack_many(core_db, vec![pkg_to_query])
+    }
+
+    /// Same as [`Self::get_pkg_stack`], but resolves every requested root in
+    /// a single combined traversal of the repository indexes instead of
+    /// repeating a full traversal per root, so roots that share a
+    /// dependency only expand it once.
+    fn get_pkg_stack_many(
+        core_db: &Database,
+        pkg_to_queries: Vec<PkgToQuery>,
+    ) -> Result<Vec<PkgDependencyNode>, LpmError<MainError>> {
         let index_db_list = db::get_repositories(core_db)?;
         if index_db_list.is_empty() {
             info!("No repository has been found within the database.");
-            return Err(RepositoryErrorKind::PackageNotFound(pkg_to_query.name).to_lpm_err())?;
+            let root_names: Vec<String> = pkg_to_queries
+                .into_iter()
+                .map(|pkg_to_query| pkg_to_query.name)
+                .collect();
+            return Err(RepositoryErrorKind::PackageNotFound(root_names.join(", ")).to_lpm_err())?;
         }
 
-        let index = find_pkg_index(&index_db_list, &pkg_to_query)?;
+        let mut nodes: Vec<PkgDependencyNode> = Vec::with_capacity(pkg_to_queries.len());
+        for pkg_to_query in pkg_to_queries {
+            let already_known = nodes.iter().any(|node| node.pkg.name == pkg_to_query.name);
+            if already_known {
+                continue;
+            }
+
+            let index = find_pkg_index(&index_db_list, &pkg_to_query)?;
+            nodes.push(PkgDependencyNode {
+                pkg: index,
+                dependency_names: Vec::new(),
+            });
+        }
 
-        let mut pkg_stack = vec![index];
         for (name, repository_address) in index_db_list {
             let repository_db_path = Path::new(db::REPOSITORY_INDEX_DB_DIR).join(&name);
             let db_file = fs::metadata(&repository_db_path)?;
@@ -72,11 +124,11 @@ impl PkgInstallTasks for PkgDataFromFs {
 
             let mut i = 0;
             loop {
-                if i >= pkg_stack.len() {
+                if i >= nodes.len() {
                     break;
                 }
 
-                let pkg = &pkg_stack[i];
+                let pkg = &nodes[i].pkg;
                 let pkg_name = format!(
                     "{}@{}{}",
                     pkg.name,
@@ -89,34 +141,40 @@ impl PkgInstallTasks for PkgDataFromFs {
                     "Failed resolving package name '{pkg_name}'"
                 );
 
-                let new_pkgs: Vec<PkgIndex> =
-                    db::PkgIndex::get_mandatory_dependencies(&index_db, &pkg_to_query)?
+                let dependency_pkg_names =
+                    db::PkgIndex::get_mandatory_dependencies(&index_db, &pkg_to_query)?;
+
+                let mut dependency_names = Vec::with_capacity(dependency_pkg_names.len());
+                for dependency_pkg_name in &dependency_pkg_names {
+                    let dependency_to_query = some_or_error!(
+                        PkgToQuery::parse(dependency_pkg_name),
+                        "Failed resolving package name '{dependency_pkg_name}'"
+                    );
+
+                    dependency_names.push(dependency_to_query.name.clone());
+
+                    let already_known = nodes
                         .iter()
-                        .map(|pkg_name| {
-                            let pkg_to_query = some_or_error!(
-                                PkgToQuery::parse(pkg_name),
-                                "Failed resolving package name '{pkg_name}'"
-                            );
-
-                            PkgIndex {
-                                name: pkg_to_query.name.clone(),
+                        .any(|node| node.pkg.name == dependency_to_query.name);
+                    if !already_known {
+                        nodes.push(PkgDependencyNode {
+                            pkg: PkgIndex {
+                                name: dependency_to_query.name.clone(),
                                 repository_address: repository_address.clone(),
-                                version: pkg_to_query.version_struct(),
-                            }
-                        })
-                        .collect();
+                                version: dependency_to_query.version_struct(),
+                            },
+                            dependency_names: Vec::new(),
+                        });
+                    }
+                }
 
-                pkg_stack.extend(new_pkgs);
+                nodes[i].dependency_names = dependency_names;
 
                 i += 1;
             }
         }
 
-        // Do not have same package with multiple versions. Which
-        // might happen when same package exists in multiple repositories.
-        pkg_stack.dedup_by_key(|t| t.name.clone());
-
-        Ok(pkg_stack)
+        Ok(nodes)
     }
 
     fn pre_install_task(path: &Path) -> Result<Self, LpmError<MainError>> {
@@ -126,6 +184,9 @@ impl PkgInstallTasks for PkgDataFromFs {
         info!("Validating files..");
         pkg.start_validate_task()?;
 
+        info!("Verifying checksums..");
+        pkg.verify_checksums()?;
+
         Ok(pkg)
     }
 
@@ -137,32 +198,47 @@ impl PkgInstallTasks for PkgDataFromFs {
         info!("Syncing with package database..");
         let pkg_id = self.insert_to_db(core_db, src_pkg_id)?;
 
-        if let Err(err) = self.scripts.execute_script(ScriptPhase::PreInstall) {
+        // Commit the package/file rows now, before any filesystem work
+        // happens, so a kill further down leaves a durable non-`installed`
+        // row behind for `recover()` to act on instead of an uncommitted
+        // transaction that SQLite silently discards on next open. A
+        // *handled* error past this point is no longer undone by rolling
+        // this transaction back (there is nothing left to roll back); it is
+        // undone explicitly by `abort_install`, which runs the same
+        // disk/row cleanup `recover()` would run for a crash, so a handled
+        // failure never leaves a lingering row for `is_package_exists` to
+        // mistake for a successful install.
+        if let Err(err) = transaction_op(core_db, Transaction::Commit) {
             transaction_op(core_db, Transaction::Rollback)?;
+            return Err(err)?;
+        };
+        set_package_state(core_db, pkg_id, PackageState::Downloading)?;
+
+        if let Err(err) = self.scripts.execute_script(ScriptPhase::PreInstall) {
+            abort_install(core_db, pkg_id)?;
             return Err(err);
         }
 
+        set_package_state(core_db, pkg_id, PackageState::Installing)?;
+
         info!("Installing package files into system..");
         if let Err(err) = self.install() {
-            transaction_op(core_db, Transaction::Rollback)?;
+            abort_install(core_db, pkg_id)?;
             return Err(err);
         };
 
         info!("Cleaning temporary files..");
         if let Err(err) = self.cleanup() {
-            transaction_op(core_db, Transaction::Rollback)?;
+            abort_install(core_db, pkg_id)?;
             return Err(err)?;
         };
 
         if let Err(err) = self.scripts.execute_script(ScriptPhase::PostInstall) {
-            transaction_op(core_db, Transaction::Rollback)?;
+            abort_install(core_db, pkg_id)?;
             return Err(err);
         }
 
-        if let Err(err) = transaction_op(core_db, Transaction::Commit) {
-            transaction_op(core_db, Transaction::Rollback)?;
-            return Err(err)?;
-        };
+        set_package_state(core_db, pkg_id, PackageState::Installed)?;
 
         info!("Installation transaction completed.");
 
@@ -175,6 +251,26 @@ impl PkgInstallTasks for PkgDataFromFs {
         self.copy_programs()
     }
 
+    // Verifies every file's digest against the checksum recorded for it.
+    // Called from `pre_install_task`, before any script runs, so a
+    // corrupted or tampered download is rejected before its `PreInstall`
+    // script is ever executed, not just before its files are written.
+    fn verify_checksums(&self) -> Result<(), LpmError<MainError>> {
+        let source_path = get_pkg_tmp_output_path(&self.path).join("program");
+
+        for file in &self.meta_dir.files.0 {
+            let from = source_path.join(&file.path);
+            let digest = file.checksum_kind.digest(&from)?;
+
+            if digest != file.checksum {
+                return Err(PackageErrorKind::ChecksumMismatch(file.path.display().to_string())
+                    .to_lpm_err())?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn copy_programs(&self) -> Result<(), LpmError<MainError>> {
         let source_path = get_pkg_tmp_output_path(&self.path).join("program");
 
@@ -192,6 +288,87 @@ impl PkgInstallTasks for PkgDataFromFs {
         Ok(())
     }
 
+    /// Moves an already installed package onto the files/metadata carried by
+    /// `self`, reconciling the `files` table instead of inserting a fresh
+    /// package row.
+    ///
+    /// Follows the same early-commit-then-explicit-state shape as
+    /// `start_install_task`: the row is committed and marked before any
+    /// filesystem work happens, so a kill mid-upgrade leaves a durable
+    /// non-`installed` row for `recover()` to find instead of a transaction
+    /// that SQLite silently discards on reopen. Handled failures past that
+    /// point reuse `abort_install` as-is — an upgrade that fails partway is
+    /// rolled back the same way a fresh install is, rather than leaving a
+    /// package half-upgraded.
+    fn upgrade_install_task(
+        &self,
+        core_db: &Database,
+        installed_pkg_id: i64,
+    ) -> Result<i64, LpmError<MainError>> {
+        info!("Syncing with package database..");
+        let pkg_id = self.update_in_db(core_db, installed_pkg_id)?;
+
+        let new_paths: Vec<String> = self
+            .meta_dir
+            .files
+            .0
+            .iter()
+            .map(|file| Path::new("/").join(&file.path).display().to_string())
+            .collect();
+
+        let stale_paths: Vec<String> = get_package_file_paths(core_db, installed_pkg_id)?
+            .into_iter()
+            .filter(|existing| !new_paths.contains(existing))
+            .collect();
+
+        if let Err(err) = transaction_op(core_db, Transaction::Commit) {
+            transaction_op(core_db, Transaction::Rollback)?;
+            return Err(err)?;
+        };
+        set_package_state(core_db, pkg_id, PackageState::Installing)?;
+
+        if let Err(err) = self.scripts.execute_script(ScriptPhase::PreInstall) {
+            abort_install(core_db, pkg_id)?;
+            return Err(err);
+        }
+
+        info!("Installing package files into system..");
+        if let Err(err) = self.install() {
+            abort_install(core_db, pkg_id)?;
+            return Err(err);
+        };
+
+        for path in &stale_paths {
+            debug!("Removing stale file {}", path);
+            if let Err(err) = fs::remove_file(path) {
+                abort_install(core_db, pkg_id)?;
+                return Err(err)?;
+            }
+        }
+
+        if let Err(err) = delete_file_records(core_db, pkg_id, &stale_paths) {
+            abort_install(core_db, pkg_id)?;
+            return Err(err);
+        }
+
+        info!("Cleaning temporary files..");
+        if let Err(err) = self.cleanup() {
+            abort_install(core_db, pkg_id)?;
+            return Err(err)?;
+        };
+
+        if let Err(err) = self.scripts.execute_script(ScriptPhase::PostInstall) {
+            abort_install(core_db, pkg_id)?;
+            return Err(err);
+        }
+
+        set_package_state(core_db, pkg_id, PackageState::Installed)?;
+
+        info!("Upgrade transaction completed.");
+
+        Ok(pkg_id)
+    }
+
     fn copy_scripts(&self) -> Result<(), LpmError<MainError>> {
         let pkg_scripts_path = Path::new(PKG_SCRIPTS_DIR)
             .join(&self.meta_dir.meta.name)
@@ -215,61 +392,294 @@ impl PkgInstallTasks for PkgDataFromFs {
     }
 }
 
+/// Validates that the dependency graph has no cycles by running Kahn's
+/// algorithm over it. Only used for validation up front; the actual
+/// install order below is driven by each thread waiting on its own direct
+/// dependencies rather than this flattened order, since installs of
+/// independent branches should not be serialized.
+fn assert_acyclic(nodes: &[PkgDependencyNode]) -> Result<(), LpmError<MainError>> {
+    let names_and_deps: Vec<(&str, &[String])> = nodes
+        .iter()
+        .map(|node| (node.pkg.name.as_str(), node.dependency_names.as_slice()))
+        .collect();
+
+    assert_acyclic_names(&names_and_deps)
+}
+
+/// Name/dependency-only core of [`assert_acyclic`], kept separate from
+/// [`PkgDependencyNode`] (which embeds a [`PkgIndex`]) so the cycle-detection
+/// algorithm itself can be unit tested without constructing one.
+fn assert_acyclic_names(nodes: &[(&str, &[String])]) -> Result<(), LpmError<MainError>> {
+    let mut in_degree: HashMap<&str, usize> = HashMap::new();
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    for (name, dependency_names) in nodes {
+        in_degree.entry(name).or_insert(0);
+        for dependency_name in *dependency_names {
+            *in_degree.entry(name).or_insert(0) += 1;
+            dependents
+                .entry(dependency_name.as_str())
+                .or_default()
+                .push(name);
+        }
+    }
+
+    let mut queue: VecDeque<&str> = in_degree
+        .iter()
+        .filter(|(_, degree)| **degree == 0)
+        .map(|(name, _)| *name)
+        .collect();
+
+    let mut resolved = 0;
+    while let Some(name) = queue.pop_front() {
+        resolved += 1;
+
+        if let Some(dependent_names) = dependents.get(name) {
+            for dependent_name in dependent_names {
+                let degree = in_degree.get_mut(dependent_name).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push_back(dependent_name);
+                }
+            }
+        }
+    }
+
+    if resolved != nodes.len() {
+        return Err(PackageErrorKind::DependencyCycle.to_lpm_err())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names_and_deps<'a>(pairs: &'a [(&'a str, &'a [&'a str])]) -> Vec<(&'a str, Vec<String>)> {
+        pairs
+            .iter()
+            .map(|(name, deps)| (*name, deps.iter().map(|d| d.to_string()).collect()))
+            .collect()
+    }
+
+    fn assert_acyclic_owned(pairs: Vec<(&str, Vec<String>)>) -> Result<(), LpmError<MainError>> {
+        let borrowed: Vec<(&str, &[String])> =
+            pairs.iter().map(|(name, deps)| (*name, deps.as_slice())).collect();
+        assert_acyclic_names(&borrowed)
+    }
+
+    #[test]
+    fn empty_graph_is_acyclic() {
+        assert!(assert_acyclic_owned(Vec::new()).is_ok());
+    }
+
+    #[test]
+    fn single_node_without_dependencies_is_acyclic() {
+        let pairs = names_and_deps(&[("a", &[])]);
+        assert!(assert_acyclic_owned(pairs).is_ok());
+    }
+
+    #[test]
+    fn linear_chain_is_acyclic() {
+        let pairs = names_and_deps(&[("a", &["b"]), ("b", &["c"]), ("c", &[])]);
+        assert!(assert_acyclic_owned(pairs).is_ok());
+    }
+
+    #[test]
+    fn diamond_shared_dependency_is_acyclic() {
+        let pairs = names_and_deps(&[
+            ("a", &["b", "c"]),
+            ("b", &["d"]),
+            ("c", &["d"]),
+            ("d", &[]),
+        ]);
+        assert!(assert_acyclic_owned(pairs).is_ok());
+    }
+
+    #[test]
+    fn direct_cycle_is_rejected() {
+        let pairs = names_and_deps(&[("a", &["b"]), ("b", &["a"])]);
+        assert!(assert_acyclic_owned(pairs).is_err());
+    }
+
+    #[test]
+    fn self_dependency_is_rejected() {
+        let pairs = names_and_deps(&[("a", &["a"])]);
+        assert!(assert_acyclic_owned(pairs).is_err());
+    }
+
+    #[test]
+    fn cycle_in_subset_of_larger_graph_is_rejected() {
+        let pairs = names_and_deps(&[
+            ("a", &["b"]),
+            ("b", &["c"]),
+            ("c", &["b"]),
+            ("d", &[]),
+        ]);
+        assert!(assert_acyclic_owned(pairs).is_err());
+    }
+}
+
+/// Undoes a package whose row was already committed (see `start_install_task`)
+/// but which then failed a later, handled phase of the install: removes any
+/// files it managed to write to disk and deletes its `packages`/`files`
+/// rows. This is the same cleanup `recover()` performs for a crashed
+/// install, run immediately instead of on the next startup, so a handled
+/// failure never leaves a row behind for `is_package_exists` to mistake for
+/// a successful install.
+fn abort_install(core_db: &Database, pkg_id: i64) -> Result<(), LpmError<MainError>> {
+    set_package_state(core_db, pkg_id, PackageState::Failed)?;
+
+    for path in get_package_file_paths(core_db, pkg_id)? {
+        if let Err(err) = fs::remove_file(&path) {
+            warning!("Failed to remove '{}' while aborting a failed install: {}", path, err);
+        }
+    }
+
+    delete_package(core_db, pkg_id)
+}
+
 pub fn install_from_repository(
     core_db: &Database,
     pkg_name: &str,
     _src_pkg_id: Option<i64>,
 ) -> Result<(), LpmError<MainError>> {
+    // Clean up after any install left behind by a crash before trusting
+    // `is_package_exists` below. There is no dedicated startup hook in this
+    // binary, so every repository install and upgrade entry point in this
+    // module runs it defensively (`install_from_lod_file` does not); it is a
+    // no-op once nothing is left to recover.
+    crate::recover::recover(core_db)?;
+
     let pkg_to_query = PkgToQuery::parse(pkg_name)
         .ok_or_else(|| PackageErrorKind::InvalidPackageName(pkg_name.to_owned()).to_lpm_err())?;
 
     if is_package_exists(core_db, &pkg_to_query.name)? {
         logger::info!(
-            "Package '{}' already installed on your machine.",
+            "Package '{}' already installed on your machine, checking for an upgrade..",
             pkg_to_query.to_string()
         );
-        return Ok(());
+        return upgrade_from_repository(core_db, pkg_name, false);
     }
 
     // Find package stack(package itself and it's dependencies)
     let pkg_stack = PkgDataFromFs::get_pkg_stack(core_db, pkg_to_query)?;
 
-    let mut thread_handlers = Vec::new();
+    assert_acyclic(&pkg_stack)?;
+    schedule_install(pkg_stack)
+}
 
-    // - Download all in parallel
-    // - Extract all in parallel
-    // - Install all in parallel
+/// Resolves the combined dependency closure of every requested package in
+/// one pass, deduplicating shared dependencies so a common transitive
+/// dependency is downloaded and installed exactly once, then installs the
+/// whole set under a single coordinated schedule.
+pub fn install_many(core_db: &Database, pkg_names: &[&str]) -> Result<(), LpmError<MainError>> {
+    crate::recover::recover(core_db)?;
 
-    // - Insert the source package, get the src id and insert the rest of them in parallel
+    let mut roots_to_resolve = Vec::new();
+    let mut already_installed = Vec::new();
 
-    let shared_data: Arc<RwLock<Option<i64>>> = Arc::new(RwLock::new(None));
-    let root_pkg_filename = pkg_stack.first().unwrap().pkg_filename();
-    for item in pkg_stack {
-        let shared_data = Arc::clone(&shared_data);
-        let is_root_pkg = item.pkg_filename() == root_pkg_filename;
+    for &pkg_name in pkg_names {
+        let pkg_to_query = PkgToQuery::parse(pkg_name)
+            .ok_or_else(|| PackageErrorKind::InvalidPackageName(pkg_name.to_owned()).to_lpm_err())?;
+
+        if is_package_exists(core_db, &pkg_to_query.name)? {
+            already_installed.push(pkg_to_query.name);
+            continue;
+        }
+
+        roots_to_resolve.push(pkg_to_query);
+    }
+
+    let merged_stack = if roots_to_resolve.is_empty() {
+        Vec::new()
+    } else {
+        PkgDataFromFs::get_pkg_stack_many(core_db, roots_to_resolve)?
+    };
+
+    for pkg_name in already_installed {
+        logger::info!(
+            "Package '{}' already installed on your machine, checking for an upgrade..",
+            pkg_name
+        );
+        upgrade_from_repository(core_db, &pkg_name, false)?;
+    }
+
+    if merged_stack.is_empty() {
+        return Ok(());
+    }
+
+    assert_acyclic(&merged_stack)?;
+    schedule_install(merged_stack)
+}
+
+/// Installs a resolved dependency DAG in topological order. Every thread
+/// downloads and extracts its own package concurrently (no ordering
+/// constraint there), then waits on `completed` for its direct
+/// dependencies' package ids before calling `start_install_task`, so
+/// dependencies are always installed and committed strictly before their
+/// dependents.
+fn schedule_install(pkg_stack: Vec<PkgDependencyNode>) -> Result<(), LpmError<MainError>> {
+    // `None` marks a package name whose thread finished without producing a
+    // row id, i.e. it failed somewhere between `download_file` and
+    // `start_install_task`. Every thread notifies on *both* success and
+    // failure so a dependent thread parked in `condvar.wait` below is always
+    // woken back up instead of blocking forever on a package that is never
+    // coming.
+    let completed: Arc<(Mutex<HashMap<String, Option<i64>>>, Condvar)> =
+        Arc::new((Mutex::new(HashMap::new()), Condvar::new()));
+
+    let mut thread_handlers = Vec::new();
+    for node in pkg_stack {
+        let completed = Arc::clone(&completed);
+        let pkg_path = node.pkg.pkg_output_path(super::EXTRACTION_OUTPUT_PATH);
+        let pkg_name = node.pkg.name.clone();
 
-        let pkg_path = item.pkg_output_path(super::EXTRACTION_OUTPUT_PATH);
         let handler = thread::spawn(move || -> Result<i64, LpmError<MainError>> {
-            let core_db = crate::open_core_db_connection()?;
-            download_file(&item.pkg_url(), &pkg_path)?;
-            let pkg = PkgDataFromFs::pre_install_task(&pkg_path)?;
-            info!("Package installation started for {}", pkg_path.display());
-
-            let pkg_id = if is_root_pkg {
-                let pkg_id = pkg.start_install_task(&core_db, *shared_data.read().unwrap())?;
-                *shared_data.write().unwrap() = Some(pkg_id); // Write pkg_id to shared_data for the first element
-
-                pkg_id
-            } else {
-                while shared_data.read().unwrap().is_none() {
-                    thread::yield_now(); // Wait until shared_data has a value
-                }
-                let pkg_id = pkg.start_install_task(&core_db, *shared_data.read().unwrap())?; // Use shared_data for other elements
+            let result = (|| -> Result<i64, LpmError<MainError>> {
+                let core_db = crate::open_core_db_connection()?;
+                download_file(&node.pkg.pkg_url(), &pkg_path)?;
+                let pkg = PkgDataFromFs::pre_install_task(&pkg_path)?;
+                info!("Package installation started for {}", pkg_path.display());
+
+                // The schema only tracks a single `depended_package_id` per
+                // package, so when there is more than one direct dependency we
+                // record the first one as the immediate parent.
+                let src_pkg_id = if node.dependency_names.is_empty() {
+                    None
+                } else {
+                    let (lock, condvar) = &*completed;
+                    let mut completed_ids = lock.lock().unwrap();
+                    while !node
+                        .dependency_names
+                        .iter()
+                        .all(|dependency_name| completed_ids.contains_key(dependency_name))
+                    {
+                        completed_ids = condvar.wait(completed_ids).unwrap();
+                    }
+
+                    if let Some(failed_dependency) = node
+                        .dependency_names
+                        .iter()
+                        .find(|dependency_name| completed_ids[*dependency_name].is_none())
+                    {
+                        return Err(
+                            PackageErrorKind::DependencyFailed(failed_dependency.clone()).to_lpm_err()
+                        )?;
+                    }
+
+                    completed_ids[&node.dependency_names[0]]
+                };
 
-                pkg_id
-            };
+                pkg.start_install_task(&core_db, src_pkg_id)
+            })();
 
-            Ok(pkg_id)
+            let (lock, condvar) = &*completed;
+            lock.lock().unwrap().insert(pkg_name, result.as_ref().ok().copied());
+            condvar.notify_all();
+
+            result
         });
 
         thread_handlers.push(handler);
@@ -282,6 +692,54 @@ pub fn install_from_repository(
     Ok(())
 }
 
+/// Upgrades an already installed package to the version currently offered
+/// by the configured repositories.
+///
+/// When `force` is `true`, the package is reinstalled even if the
+/// repository only offers the same version that is already installed,
+/// mirroring `cargo install`'s `--force` behavior.
+pub fn upgrade_from_repository(
+    core_db: &Database,
+    pkg_name: &str,
+    force: bool,
+) -> Result<(), LpmError<MainError>> {
+    crate::recover::recover(core_db)?;
+
+    let pkg_to_query = PkgToQuery::parse(pkg_name)
+        .ok_or_else(|| PackageErrorKind::InvalidPackageName(pkg_name.to_owned()).to_lpm_err())?;
+
+    let installed_pkg = get_installed_package(core_db, &pkg_to_query.name)?
+        .ok_or_else(|| PackageErrorKind::PackageNotInstalled(pkg_to_query.name.clone()).to_lpm_err())?;
+
+    let index = find_pkg_index(&db::get_repositories(core_db)?, &pkg_to_query)?;
+    let repository_version = InstalledVersion {
+        major: index.version.major as i64,
+        minor: index.version.minor as i64,
+        patch: index.version.patch as i64,
+    };
+
+    if !force && repository_version <= installed_pkg.version {
+        info!(
+            "'{}' is already up to date.",
+            pkg_to_query.name
+        );
+        return Ok(());
+    }
+
+    info!(
+        "Upgrading '{}' to version {}..",
+        pkg_to_query.name, index.version.readable_format
+    );
+
+    let pkg_path = index.pkg_output_path(super::EXTRACTION_OUTPUT_PATH);
+    download_file(&index.pkg_url(), &pkg_path)?;
+    let pkg = PkgDataFromFs::pre_install_task(&pkg_path)?;
+
+    pkg.upgrade_install_task(core_db, installed_pkg.id)?;
+
+    Ok(())
+}
+
 pub fn install_from_lod_file(
     core_db: &Database,
     pkg_path: &str,
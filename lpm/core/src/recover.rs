@@ -0,0 +1,32 @@
+use db::pkg::{delete_package, get_package_file_paths, get_packages_not_in_state, PackageState};
+use ehandle::{lpm::LpmError, MainError};
+use logger::warning;
+use min_sqlite3_sys::prelude::*;
+use std::fs;
+
+/// Scans for packages left in a non-`installed` state by a crash or kill
+/// mid-`install_from_repository`, and rolls each of them back out of `/`
+/// using the `files` table recorded for it. Called at the start of every
+/// repository install and upgrade entry point, before any new install is
+/// attempted.
+pub fn recover(core_db: &Database) -> Result<(), LpmError<MainError>> {
+    let crashed_packages = get_packages_not_in_state(core_db, PackageState::Installed)?;
+
+    for pkg in crashed_packages {
+        warning!(
+            "Package '{}' was left in an incomplete '{:?}' state, most likely from an interrupted install. Rolling it back..",
+            pkg.name,
+            pkg.state
+        );
+
+        for path in get_package_file_paths(core_db, pkg.id)? {
+            if let Err(err) = fs::remove_file(&path) {
+                warning!("Failed to remove '{}' while recovering '{}': {}", path, pkg.name, err);
+            }
+        }
+
+        delete_package(core_db, pkg.id)?;
+    }
+
+    Ok(())
+}
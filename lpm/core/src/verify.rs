@@ -0,0 +1,40 @@
+use common::checksum::ChecksumKind;
+use db::pkg::{get_installed_package, get_package_file_checksums};
+use ehandle::{lpm::LpmError, pkg::PackageErrorKind, ErrorCommons, MainError};
+use logger::info;
+use min_sqlite3_sys::prelude::*;
+use std::{path::Path, str::FromStr};
+
+/// Re-hashes every file recorded for an installed package and compares it
+/// against the checksum stored at install time, to detect post-install
+/// drift or corruption on disk.
+pub fn verify(core_db: &Database, pkg_name: &str) -> Result<(), LpmError<MainError>> {
+    let installed_pkg = get_installed_package(core_db, pkg_name)?
+        .ok_or_else(|| PackageErrorKind::PackageNotInstalled(pkg_name.to_owned()).to_lpm_err())?;
+
+    let files = get_package_file_checksums(core_db, installed_pkg.id)?;
+
+    let mut drifted_paths = Vec::new();
+    for file in &files {
+        let checksum_kind = ChecksumKind::from_str(&file.checksum_kind).map_err(|_| {
+            PackageErrorKind::UnsupportedChecksumKind(file.checksum_kind.clone()).to_lpm_err()
+        })?;
+
+        let digest = checksum_kind.digest(Path::new(&file.absolute_path))?;
+        if digest != file.checksum {
+            drifted_paths.push(file.absolute_path.clone());
+        }
+    }
+
+    if !drifted_paths.is_empty() {
+        return Err(PackageErrorKind::ChecksumMismatch(drifted_paths.join(", ")).to_lpm_err())?;
+    }
+
+    info!(
+        "'{}' passed the integrity check; {} file(s) verified.",
+        pkg_name,
+        files.len()
+    );
+
+    Ok(())
+}